@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use horner::{eval_any_rank_polynomial, eval_known_rank_polynomial};
+
+/// Compares the const-generic unrolled evaluator against the plain slice loop it used to
+/// forward to, at ranks 4 and 9, to check the unrolling is actually paying for itself.
+fn bench_eval_known_rank_polynomial(c: &mut Criterion) {
+  let coefficients_4 = [23_i64, 0, 27, -5];
+  let coefficients_9 = [23_i64, 0, 27, 0, -5, 0, 0, 0, 0];
+
+  c.bench_function("eval_known_rank_polynomial, rank 4", |b| {
+    b.iter(|| eval_known_rank_polynomial(black_box(99), black_box(&coefficients_4)))
+  });
+  c.bench_function("eval_any_rank_polynomial, rank 4", |b| {
+    b.iter(|| eval_any_rank_polynomial(black_box(99), black_box(&coefficients_4)))
+  });
+
+  c.bench_function("eval_known_rank_polynomial, rank 9", |b| {
+    b.iter(|| eval_known_rank_polynomial(black_box(99), black_box(&coefficients_9)))
+  });
+  c.bench_function("eval_any_rank_polynomial, rank 9", |b| {
+    b.iter(|| eval_any_rank_polynomial(black_box(99), black_box(&coefficients_9)))
+  });
+}
+
+criterion_group!(benches, bench_eval_known_rank_polynomial);
+criterion_main!(benches);
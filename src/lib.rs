@@ -1,5 +1,15 @@
+//! `no_std` when the default `std` feature is disabled. Enable the `libm` feature to get the
+//! float-oriented FMA evaluators without `std`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::ops::{Add, Mul};
 use num_traits::ops::mul_add::MulAddAssign;
 use num_traits::Zero;
+#[cfg(any(feature = "std", feature = "libm"))]
+use num_traits::Float;
 
 /// Evaluate a polynomial of arbitrary rank using Horner's method.
 ///
@@ -57,8 +67,7 @@ pub fn eval_any_rank_polynomial<T: Zero + MulAddAssign + Copy> (x: T, coefficien
 {
   if let Some((&k, coefficients)) = coefficients.split_first() {
     let mut val = k;
-    let mut it = coefficients.iter();
-    while let Some(&k) = it.next() {
+    for &k in coefficients {
       val.mul_add_assign(x, k);
     }
     val
@@ -69,9 +78,11 @@ pub fn eval_any_rank_polynomial<T: Zero + MulAddAssign + Copy> (x: T, coefficien
 
 /// Evaluate a polynomial of rank known at compile-time using Horner's method.
 ///
-/// For now this function simply calls [eval_any_rank_polynomial], but the idea
-/// is that in the future we may be able to optimize our code further in the case
-/// where the rank of the polynomial is known at compile-time.
+/// Unlike [eval_any_rank_polynomial], `N` is part of the type here, so the loop below runs a
+/// fixed, statically-known number of times for any given monomorphization. That's enough for
+/// the optimizer to unroll it into a flat, branch-free sequence of multiply-adds on its own —
+/// no bounds checks survive once `N` is a compile-time constant and the loop is inlined into
+/// the caller.
 ///
 /// Example usage:
 ///
@@ -84,5 +95,256 @@ pub fn eval_any_rank_polynomial<T: Zero + MulAddAssign + Copy> (x: T, coefficien
 /// See also: [eval_any_rank_polynomial]
 pub fn eval_known_rank_polynomial<T: Zero + MulAddAssign + Copy, const N: usize> (x: T, coefficients: &[T; N]) -> T
 {
-  eval_any_rank_polynomial(x, coefficients)
+  if N == 0 {
+    return T::zero();
+  }
+
+  let mut val = coefficients[0];
+  for &k in &coefficients[1..] {
+    val.mul_add_assign(x, k);
+  }
+  val
+}
+
+/// Evaluate a polynomial of arbitrary rank using Horner's method, rounding with a single
+/// fused multiply-add at every step.
+///
+/// [eval_any_rank_polynomial] accumulates with [MulAddAssign], which `num-traits` does not
+/// guarantee lowers to a hardware FMA. On `f32`/`f64` this function instead calls
+/// [Float::mul_add] directly, which both rounds once per step instead of twice (better
+/// accuracy) and, on targets with an FMA instruction, is faster.
+///
+/// Requires the `libm` feature (or `std`) so that [Float] is available without a hardware
+/// floating-point runtime being assumed to already be present.
+///
+/// ```
+/// use horner::eval_any_rank_polynomial_fma;
+///
+/// // Evaluating the polynomial 72𝑥²+81𝑥+99 with 𝑥 = 5
+/// let val = eval_any_rank_polynomial_fma(5.0, &[72.0, 81.0, 99.0]);
+///
+/// assert_eq!(val, 72.0 * 5.0_f64.powi(2) + 81.0 * 5.0 + 99.0);
+/// ```
+///
+/// ```
+/// # use horner::eval_any_rank_polynomial_fma;
+/// assert_eq!(0.0, eval_any_rank_polynomial_fma(222.0, &[]));
+/// ```
+///
+/// See also: [eval_any_rank_polynomial], [eval_known_rank_polynomial_fma]
+#[cfg(any(feature = "std", feature = "libm"))]
+pub fn eval_any_rank_polynomial_fma<T: Float> (x: T, coefficients: &[T]) -> T
+{
+  if let Some((&k, coefficients)) = coefficients.split_first() {
+    let mut val = k;
+    for &k in coefficients {
+      val = x.mul_add(val, k);
+    }
+    val
+  } else {
+    T::zero()
+  }
+}
+
+/// Evaluate a polynomial of rank known at compile-time using Horner's method, rounding with
+/// a single fused multiply-add at every step.
+///
+/// Like [eval_known_rank_polynomial], `N` being part of the type here means the loop below
+/// runs a fixed, statically-known number of times per monomorphization, so the optimizer can
+/// unroll it; see [eval_any_rank_polynomial_fma] for why the FMA itself matters.
+///
+/// ```
+/// use horner::eval_known_rank_polynomial_fma;
+///
+/// assert_eq!(0.0, eval_known_rank_polynomial_fma(-4.0, &[1.0, 4.0]));
+/// ```
+///
+/// See also: [eval_any_rank_polynomial_fma]
+#[cfg(any(feature = "std", feature = "libm"))]
+pub fn eval_known_rank_polynomial_fma<T: Float, const N: usize> (x: T, coefficients: &[T; N]) -> T
+{
+  if N == 0 {
+    return T::zero();
+  }
+
+  let mut val = coefficients[0];
+  for &k in &coefficients[1..] {
+    val = x.mul_add(val, k);
+  }
+  val
+}
+
+/// Evaluate a polynomial of arbitrary rank using Estrin's scheme.
+///
+/// [eval_any_rank_polynomial] builds a strictly sequential chain of multiply-adds, so each
+/// step has to wait for the result of the previous one. Estrin's scheme instead reorders the
+/// coefficients lowest-degree first (`coefficients[0]` here is the highest-degree term, same
+/// as [eval_any_rank_polynomial]) and pairs them up into `𝑏ⱼ = 𝑎₂ⱼ + 𝑥×𝑎₂ⱼ₊₁`, then treats the
+/// `𝑏ⱼ` as the coefficients of a polynomial in `𝑥²` and repeats, `𝑐ₖ = 𝑏₂ₖ + 𝑥²×𝑏₂ₖ₊₁`, and so
+/// on with `𝑥⁴`, `𝑥⁸`, … at each subsequent level, until a single value remains.
+///
+/// Because the multiply-adds within a level don't depend on each other, a superscalar CPU
+/// can issue them in parallel, giving roughly logarithmic latency in the coefficient count
+/// instead of linear. If the coefficient count at a level is odd, the leftover coefficient
+/// is carried up to the next level unchanged.
+///
+/// As with [eval_any_rank_polynomial], an empty slice of coefficients evaluates to `T::zero()`.
+///
+/// Builds the intermediate levels on the heap, so it needs `alloc` even without `std`.
+///
+/// Squaring to get `𝑥²`, `𝑥⁴`, `𝑥⁸`, … can run `𝑥` well past the magnitude any *significant*
+/// coefficient needs, since it's driven by how many coefficients there are, not which of them
+/// are actually non-zero. A literal zero coefficient is skipped rather than multiplied against
+/// that overshot `𝑥` power, so for floats a zero-padded high-rank polynomial with a large `|𝑥|`
+/// still comes out right instead of poisoning the sum with `inf * 0` on a term that was never
+/// going to contribute anything. The squarings themselves are not skipped: for integers, an
+/// `𝑥` power that does not fit still overflows, however many coefficients are zero.
+///
+/// ```
+/// use horner::eval_estrin;
+///
+/// // Evaluating the polynomial 72𝑥²+81𝑥+99 with 𝑥 = 5
+/// let val = eval_estrin(5, &[72, 81, 99]);
+///
+/// // Traditional calculation.
+/// let trad = 72 * 5_i32.pow(2) + 81 * 5 + 99;
+///
+/// assert_eq!(val, trad);
+/// ```
+///
+/// ```
+/// # use horner::eval_estrin;
+/// // 23𝑥⁹+0𝑥⁸+27𝑥⁷+0𝑥⁶-5𝑥⁵+0𝑥⁴+0𝑥³+0𝑥²+0𝑥ⁱ+0𝑥⁰
+/// let val = eval_estrin(99_i128, &[23, 0, 27, 0, -5, 0, 0, 0, 0, 0]);
+/// let trad = 23 * 99_i128.pow(9) + 27 * 99_i128.pow(7) - 5 * 99_i128.pow(5);
+///
+/// assert_eq!(val, trad);
+/// ```
+///
+/// ```
+/// # use horner::eval_estrin;
+/// assert_eq!(0, eval_estrin(222, &[]));
+/// ```
+///
+/// A rank-20 polynomial that's just the constant `2` squares `𝑥` up through `𝑥¹⁶` along the
+/// way, purely as a side effect of there being 20 coefficients to pair up. With `𝑥 = 10` that
+/// stops at `10_i64.pow(16)`, which fits `i64`; `𝑥` is not squared again after the last level
+/// (`10_i64.pow(32)` doesn't fit). With `𝑥 = 1e20` the `f64` case reaches `inf` at `𝑥¹⁶` and
+/// would hand back `NaN` instead of `2.0` if the zero coefficients weren't skipped.
+/// ```
+/// # use horner::eval_estrin;
+/// let mut coefficients = [0_i64; 20];
+/// coefficients[19] = 2;
+/// assert_eq!(2, eval_estrin(10_i64, &coefficients));
+/// assert_eq!(2, eval_estrin(-7_i64, &coefficients));
+///
+/// let mut coefficients = [0.0_f64; 20];
+/// coefficients[19] = 2.0;
+/// assert_eq!(2.0, eval_estrin(1e20_f64, &coefficients));
+/// ```
+///
+/// See also: [eval_any_rank_polynomial]
+pub fn eval_estrin<T: Zero + Copy + Add<Output = T> + Mul<Output = T>> (x: T, coefficients: &[T]) -> T
+{
+  if coefficients.is_empty() {
+    return T::zero();
+  }
+
+  // Reorder to lowest-degree first so adjacent elements are `(a_{2j}, a_{2j+1})` pairs,
+  // matching the convention Estrin's scheme is usually described in.
+  let mut level: Vec<T> = coefficients.iter().rev().copied().collect();
+  let mut xp = x;
+
+  while level.len() > 1 {
+    let mut next = Vec::with_capacity(level.len().div_ceil(2));
+    let mut chunks = level.chunks_exact(2);
+    for pair in &mut chunks {
+      // A literal zero contributes nothing, so skip the multiply rather than pull `xp`
+      // into the sum: once enough levels have passed, `xp` can have grown far past the
+      // magnitude the *significant* coefficients need, and `xp * zero` would turn a
+      // should-be-zero term into `inf * 0 == NaN` for floats.
+      next.push(if pair[1].is_zero() { pair[0] } else { pair[0] + xp * pair[1] });
+    }
+    if let [leftover] = *chunks.remainder() {
+      next.push(leftover);
+    }
+    level = next;
+    // Only square `xp` if another level will actually consume it: squaring one level
+    // past the last real combine serves no purpose but can still overflow.
+    if level.len() > 1 {
+      xp = xp * xp;
+    }
+  }
+
+  level[0]
+}
+
+/// Re-exported so that the [horner!] macro can reach `Zero::zero()` from call sites
+/// without requiring downstream crates to also depend on `num-traits` directly.
+#[doc(hidden)]
+pub use num_traits as __num_traits;
+
+/// Expand `horner!(𝑥; 𝑎, 𝑏, 𝑐, 𝑑, …)` directly into the nested multiply-add expression
+/// `((𝑎×𝑥+𝑏)×𝑥+𝑐)×𝑥+𝑑` at the call site, instead of iterating over a runtime slice. As with
+/// [eval_any_rank_polynomial], `𝑎` is the highest-degree coefficient.
+///
+/// When the number of coefficients is known where you're writing the call, this lets the
+/// optimizer fully unroll and constant-fold the evaluation: no loop, no bounds checks, no
+/// slice to build. Both `𝑥` and the coefficients may be arbitrary expressions, not just
+/// literals; `𝑥` is evaluated exactly once no matter how many coefficients follow it.
+///
+/// An empty coefficient list expands to `T::zero()`, matching the empty-slice behaviour of
+/// [eval_any_rank_polynomial].
+///
+/// ```
+/// use horner::horner;
+///
+/// // Evaluating the polynomial 72𝑥²+81𝑥+99 with 𝑥 = 5
+/// let val = horner!(5; 72, 81, 99);
+///
+/// // Traditional calculation.
+/// let trad = 72 * 5_i32.pow(2) + 81 * 5 + 99;
+///
+/// assert_eq!(val, trad);
+/// ```
+///
+/// ```
+/// # use horner::horner;
+/// assert_eq!(0, horner!(222;));
+/// ```
+///
+/// See also: [eval_any_rank_polynomial], [eval_estrin]
+#[macro_export]
+macro_rules! horner {
+  ($x:expr; $($coefficients:expr),* $(,)?) => {
+    match $x {
+      x => $crate::__horner_expand!((x); $($coefficients),*),
+    }
+  };
+}
+
+/// Implementation detail of [horner!]; dispatches to [__horner_fold] with the first
+/// coefficient as the initial accumulator, or `T::zero()` if there are none.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __horner_expand {
+  (($x:expr);) => {
+    $crate::__num_traits::Zero::zero()
+  };
+  (($x:expr); $a0:expr $(, $rest:expr)*) => {
+    $crate::__horner_fold!(($x); ($a0); $($rest),*)
+  };
+}
+
+/// Implementation detail of [horner!]; folds the remaining coefficients into the
+/// accumulator one at a time, left to right, the same order [eval_any_rank_polynomial] uses.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __horner_fold {
+  (($x:expr); ($acc:expr);) => {
+    $acc
+  };
+  (($x:expr); ($acc:expr); $next:expr $(, $rest:expr)*) => {
+    $crate::__horner_fold!(($x); ($acc * $x + $next); $($rest),*)
+  };
 }